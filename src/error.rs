@@ -0,0 +1,27 @@
+use thiserror::Error as ThisError;
+
+/// The error type returned by fallible operations throughout the crate.
+#[derive(ThisError, Debug)]
+pub enum Error {
+    /// A request to YouTube or a direct media server failed.
+    #[error("there was an error while requesting data: {0}")]
+    Request(#[from] reqwest::Error),
+
+    /// An I/O operation backing a download failed.
+    #[error("an I/O operation failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A response did not match the shape `rustube` expects.
+    #[error("the server responded unexpectedly: {0}")]
+    UnexpectedResponse(String),
+
+    /// Muxing the downloaded audio/video tracks with `ffmpeg` failed.
+    #[cfg(feature = "mux")]
+    #[error("failed to mux the downloaded tracks: {0}")]
+    Muxing(String),
+
+    /// No `ffmpeg` binary could be found on `PATH`.
+    #[cfg(feature = "mux")]
+    #[error("no ffmpeg binary was found on PATH")]
+    MissingFFmpeg,
+}
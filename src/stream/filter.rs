@@ -0,0 +1,107 @@
+use crate::stream::Stream;
+
+/// A fluent query over a slice of [`Stream`]s.
+///
+/// Instead of hand-filtering the raw boolean fields, chain predicates like
+/// [`video_only`](StreamFilter::video_only) or
+/// [`max_resolution`](StreamFilter::max_resolution) and finish with a terminal
+/// selector such as [`best`](StreamFilter::best):
+///
+/// ```ignore
+/// let stream = StreamFilter::new(&streams)
+///     .video_only()
+///     .mime_subtype("mp4")
+///     .max_resolution(1080)
+///     .best();
+/// ```
+#[derive(Clone, Debug)]
+pub struct StreamFilter<'a> {
+    streams: Vec<&'a Stream>,
+}
+
+impl<'a> StreamFilter<'a> {
+    /// Starts a new query over `streams`.
+    #[inline]
+    pub fn new(streams: &'a [Stream]) -> Self {
+        Self { streams: streams.iter().collect() }
+    }
+
+    /// Keeps only streams that carry a video track but no audio track.
+    #[inline]
+    pub fn video_only(mut self) -> Self {
+        self.streams.retain(|s| s.includes_video_track && !s.includes_audio_track);
+        self
+    }
+
+    /// Keeps only streams that carry an audio track but no video track.
+    #[inline]
+    pub fn audio_only(mut self) -> Self {
+        self.streams.retain(|s| s.includes_audio_track && !s.includes_video_track);
+        self
+    }
+
+    /// Keeps only progressive streams (a muxed audio + video track).
+    #[inline]
+    pub fn progressive(mut self) -> Self {
+        self.streams.retain(|s| s.is_progressive);
+        self
+    }
+
+    /// Keeps only streams whose resolution does not exceed `resolution`.
+    ///
+    /// A stream with no parsed resolution (e.g. an audio-only stream) is
+    /// dropped rather than kept, since there is nothing to compare against.
+    #[inline]
+    pub fn max_resolution(mut self, resolution: u64) -> Self {
+        self.streams.retain(|s| s.resolution.map_or(false, |r| r <= resolution));
+        self
+    }
+
+    /// Keeps only streams whose audio bitrate is at least `abr`.
+    ///
+    /// A stream with no parsed audio bitrate (e.g. a video-only stream) is
+    /// dropped rather than kept, since there is nothing to compare against.
+    #[inline]
+    pub fn min_abr(mut self, abr: u64) -> Self {
+        self.streams.retain(|s| s.abr.map_or(false, |a| a >= abr));
+        self
+    }
+
+    /// Keeps only streams whose mime subtype matches `subtype` (e.g. `"mp4"`).
+    #[inline]
+    pub fn mime_subtype(mut self, subtype: &str) -> Self {
+        self.streams.retain(|s| s.mime.subtype() == subtype);
+        self
+    }
+
+    /// Returns the highest-quality matching stream, if any.
+    #[inline]
+    pub fn best(mut self) -> Option<&'a Stream> {
+        self.streams.sort_by_key(|s| Self::rank(s));
+        self.streams.last().copied()
+    }
+
+    /// Returns the lowest-quality matching stream, if any.
+    #[inline]
+    pub fn worst(mut self) -> Option<&'a Stream> {
+        self.streams.sort_by_key(|s| Self::rank(s));
+        self.streams.first().copied()
+    }
+
+    /// Returns the first matching stream in the original order, if any.
+    #[inline]
+    pub fn first(self) -> Option<&'a Stream> {
+        self.streams.first().copied()
+    }
+
+    /// Ranks a stream for `best`/`worst`, preferring resolution, then audio
+    /// bitrate, then raw bitrate.
+    #[inline]
+    fn rank(stream: &Stream) -> (u64, u64, u64) {
+        (
+            stream.resolution.unwrap_or(0),
+            stream.abr.unwrap_or(0),
+            stream.bitrate.unwrap_or(0),
+        )
+    }
+}
@@ -6,13 +6,14 @@ use std::sync::Arc;
 use chrono::{DateTime, Utc};
 #[cfg(feature = "download")]
 use futures::Stream as FuturesStream;
+#[cfg(feature = "download")]
+use futures::StreamExt;
 use mime::Mime;
 use reqwest::Client;
 #[cfg(feature = "download")]
 use tokio::{
     fs::File,
-    io::AsyncWriteExt,
-    stream::StreamExt,
+    io::{AsyncSeekExt, AsyncWriteExt},
 };
 
 use crate::{Result, TryCollect, VideoDetails};
@@ -20,8 +21,14 @@ use crate::error::Error;
 use crate::video_info::player_response::streaming_data::{AudioQuality, ColorInfo, FormatType, MimeType, ProjectionType, Quality, QualityLabel, RawFormat, SignatureCipher};
 
 use self::itags::ItagProfile;
+pub use self::filter::StreamFilter;
+#[cfg(feature = "mux")]
+pub use self::mux::Muxer;
 
+mod filter;
 mod itags;
+#[cfg(feature = "mux")]
+mod mux;
 
 #[derive(Clone, Debug)]
 pub struct Stream {
@@ -65,6 +72,142 @@ pub struct Stream {
     client: Client,
 }
 
+/// Tunables for the download path.
+///
+/// Controls how a `Stream`s resource is fetched: the size of each ranged
+/// request and how many of them are issued concurrently. The defaults pick a
+/// 10 MiB chunk size and a parallelism of four, which saturates most
+/// connections without overwhelming YouTube's edge servers.
+#[derive(Clone, Debug)]
+#[cfg(feature = "download")]
+pub struct DownloadOpts {
+    /// The size, in bytes, of each ranged `GET` request.
+    pub chunk_size: u64,
+    /// The number of ranged requests issued at the same time.
+    pub parallelism: usize,
+    /// How transient network failures are retried.
+    pub retry: RetryPolicy,
+}
+
+#[cfg(feature = "download")]
+impl Default for DownloadOpts {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            chunk_size: 10 * 1024 * 1024,
+            parallelism: 4,
+            retry: RetryPolicy::default(),
+        }
+    }
+}
+
+/// Exponential-backoff policy for retrying transient fetch failures.
+///
+/// Only transient conditions are retried — request timeouts, connection
+/// errors, truncated bodies, and `5xx`/`429` responses — while `404` (which
+/// drives the sequenced-download fallback) and every other `4xx` fail
+/// immediately. Between attempts the delay grows by `multiplier`, with a random
+/// half-jitter applied, until `max_elapsed` is exceeded.
+#[derive(Clone, Debug)]
+#[cfg(feature = "download")]
+pub struct RetryPolicy {
+    /// The delay before the first retry.
+    pub initial_delay: std::time::Duration,
+    /// The factor the delay is multiplied by after each attempt.
+    pub multiplier: f64,
+    /// The total time across which retries may be attempted.
+    pub max_elapsed: std::time::Duration,
+}
+
+#[cfg(feature = "download")]
+impl Default for RetryPolicy {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            initial_delay: std::time::Duration::from_millis(500),
+            multiplier: 2.0,
+            max_elapsed: std::time::Duration::from_secs(300),
+        }
+    }
+}
+
+/// Running state for a single retry loop governed by a [`RetryPolicy`].
+#[cfg(feature = "download")]
+struct Backoff<'a> {
+    retry: &'a RetryPolicy,
+    delay: std::time::Duration,
+    started: std::time::Instant,
+    attempt: u32,
+}
+
+#[cfg(feature = "download")]
+impl<'a> Backoff<'a> {
+    #[inline]
+    fn new(retry: &'a RetryPolicy) -> Self {
+        Self {
+            retry,
+            delay: retry.initial_delay,
+            started: std::time::Instant::now(),
+            attempt: 0,
+        }
+    }
+
+    /// Sleeps for the next backoff interval when `e` is transient and the
+    /// elapsed budget is not exhausted; otherwise returns `e`.
+    async fn retry_or(&mut self, e: Error) -> Result<()> {
+        self.attempt += 1;
+        if !Stream::is_transient_err(&e) || self.started.elapsed() >= self.retry.max_elapsed {
+            return Err(e);
+        }
+
+        // A random half-jitter keeps concurrent retries from firing in lock-step.
+        let jittered = self.delay.mul_f64(0.5 + Stream::jitter());
+        log::warn!("request failed ({}), retrying in {:?} (attempt {})", e, jittered, self.attempt);
+        tokio::time::delay_for(jittered).await;
+
+        self.delay = self.delay.mul_f64(self.retry.multiplier);
+        Ok(())
+    }
+}
+
+
+/// Receives progress and lifecycle events over the course of a download.
+///
+/// Implementors are invoked from the download loop, making it possible to
+/// drive a progress bar or compute throughput/ETA without forking the download
+/// code. Every method has a default no-op implementation, so callers only
+/// override the events they care about. Any `FnMut(u64, Option<u64>)` closure
+/// also implements the trait as a progress-only shorthand.
+#[cfg(feature = "callback")]
+pub trait DownloadCallback: Send {
+    /// Fired once, before any bytes are requested, with the total size as
+    /// reported by [`content_length`](Stream::content_length) when known.
+    #[inline]
+    fn on_start(&mut self, content_length: Option<u64>) {
+        let _ = content_length;
+    }
+
+    /// Fired after each written block with the running total of bytes written
+    /// so far (aggregated across chunks) and the overall size when known.
+    #[inline]
+    fn on_progress(&mut self, bytes_downloaded: u64, content_length: Option<u64>) {
+        let _ = (bytes_downloaded, content_length);
+    }
+
+    /// Fired once the output file has been finalized, with its resolved path.
+    #[inline]
+    fn on_complete(&mut self, path: &Path) {
+        let _ = path;
+    }
+}
+
+#[cfg(feature = "callback")]
+impl<F: FnMut(u64, Option<u64>) + Send> DownloadCallback for F {
+    #[inline]
+    fn on_progress(&mut self, bytes_downloaded: u64, content_length: Option<u64>) {
+        self(bytes_downloaded, content_length)
+    }
+}
 
 impl Stream {
     pub fn from_raw_format(raw_format: RawFormat, client: Client, video_details: Arc<VideoDetails>) -> Result<Self> {
@@ -115,9 +258,6 @@ impl Stream {
         })
     }
 
-    // todo: download in ranges
-    // todo: blocking download
-
     /// Attempts to downloads the `Stream`s resource.
     /// This will download the video to <video_id>.mp4 in the current working directory.
     #[inline]
@@ -148,21 +288,52 @@ impl Stream {
     /// This will download the video to the provided file path.
     #[cfg(feature = "download")]
     pub async fn download_to<P: AsRef<Path>>(&self, path: P) -> Result<()> {
-        log::trace!("download_to: {:?}", path.as_ref());
+        self.download_sequential(path.as_ref(), &noop_progress).await
+    }
+
+    /// The sequential download engine shared by
+    /// [`download_to`](Stream::download_to) and the `download_chunked`
+    /// fallbacks (unknown content-length, a server that
+    /// rejects or ignores ranges). `on_bytes` receives the size of every
+    /// written block, so progress keeps advancing on the fallback paths
+    /// instead of going silent.
+    #[cfg(feature = "download")]
+    async fn download_sequential(&self, path: &Path, on_bytes: &(dyn Fn(i64) + Send + Sync)) -> Result<()> {
+        log::trace!("download_sequential: {:?}", path);
         let mut file = File::create(&path).await?;
 
-        match self.download_full(&self.signature_cipher.url, &mut file).await {
+        // OTF streams are delivered as a segmented manifest rather than a
+        // single body, so they must be assembled segment by segment.
+        if self.is_otf {
+            return match self.download_otf_with(&mut file, on_bytes).await {
+                Ok(_) => {
+                    log::info!(
+                        "downloaded {} successfully to {:?}",
+                        self.video_details.video_id, path
+                    );
+                    Ok(())
+                }
+                Err(e) => {
+                    log::error!("failed to download {}: {:?}", self.video_details.video_id, e);
+                    drop(file);
+                    tokio::fs::remove_file(path).await?;
+                    Err(e)
+                }
+            };
+        }
+
+        match self.download_full(&self.signature_cipher.url, &mut file, on_bytes).await {
             Ok(_) => {
                 log::info!(
                     "downloaded {} successfully to {:?}",
-                    self.video_details.video_id, path.as_ref()
+                    self.video_details.video_id, path
                 );
                 Ok(())
             }
             Err(Error::Request(e)) if e.status().contains(&reqwest::StatusCode::NOT_FOUND) => {
                 log::error!("failed to download {}: {:?}", self.video_details.video_id, e);
-                // Some adaptive streams need to be requested with sequence numbers
-                self.download_full_seq(&mut file)
+                // A plain `GET` 404s on segmented streams; fall back to OTF.
+                self.download_otf_with(&mut file, on_bytes)
                     .await
             }
             Err(e) => {
@@ -174,65 +345,630 @@ impl Stream {
         }
     }
 
+    /// Attempts to download the `Stream`s resource using concurrent, ranged
+    /// requests.
+    ///
+    /// The total [`content_length`](Stream::content_length) is split into
+    /// `opts.chunk_size`d chunks, up to `opts.parallelism` of which are fetched
+    /// at the same time via `Range: bytes=start-end` requests and written to
+    /// the correct file offset — so chunks may complete out of order. Finished
+    /// chunks are recorded in a `.rtpart` sidecar next to `path`; if an earlier
+    /// download to `path` was interrupted, only the chunks not recorded there
+    /// are fetched again, and the existing file is not truncated.
+    ///
+    /// Servers that ignore the `Range` header (replying `200` with the full
+    /// body instead of `206`) are detected, in which case the download falls
+    /// back to the sequential [`download_to`](Stream::download_to) path.
+    #[cfg(feature = "download")]
+    pub async fn download_to_with_opts<P: AsRef<Path>>(&self, path: P, opts: &DownloadOpts) -> Result<()> {
+        self.download_chunked(path.as_ref(), opts, &noop_progress).await
+    }
+
+    /// The concurrent, ranged download engine shared by
+    /// [`download_to_with_opts`](Stream::download_to_with_opts) and its
+    /// callback-aware sibling. `on_bytes` receives the size of every written
+    /// block so a single counter can aggregate progress across all chunks.
     #[cfg(feature = "download")]
-    async fn download_full_seq(&self, file: &mut File) -> Result<()> {
-        // fixme: this implementation is **not** tested yet!
-        // To test it, I would need an url of a video, which does require sequenced downloading.
-        log::warn!(
-            "`download_full_seq` is not tested yet and probably broken!\n\
-            Please open a GitHub issue and paste the whole warning message plus the videos Id in:\n\
-            url: {}", self.signature_cipher.url.as_str()
+    async fn download_chunked(&self, path: &Path, opts: &DownloadOpts, on_bytes: &(dyn Fn(i64) + Send + Sync)) -> Result<()> {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use futures::TryStreamExt;
+
+        log::trace!("download_chunked: {:?} ({:?})", path, opts);
+
+        let content_length = match self.content_length().await {
+            Ok(content_length) => content_length,
+            // Without a known length we cannot partition the body, so fall back.
+            Err(e) => {
+                log::warn!("could not determine content-length, downloading sequentially: {:?}", e);
+                return self.download_sequential(path, on_bytes).await;
+            }
+        };
+
+        if !self.accepts_ranges().await? {
+            log::info!("server does not accept ranges, downloading sequentially");
+            return self.download_sequential(path, on_bytes).await;
+        }
+
+        // Make sure the data file exists so the per-chunk positioned writes have
+        // something to seek into, without truncating a partial download.
+        tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(path)
+            .await?;
+
+        let progress = Arc::new(tokio::sync::Mutex::new(
+            ChunkProgress::load(path, opts.chunk_size, content_length).await?
+        ));
+
+        // Resume by re-fetching only the chunks that were never marked done —
+        // the output file length is unreliable for an out-of-order writer.
+        let pending: Vec<(usize, Range<u64>)> = {
+            let progress = progress.lock().await;
+            if progress.is_complete() {
+                progress.remove().await;
+                log::info!("{} is already fully downloaded", self.video_details.video_id);
+                return Ok(());
+            }
+            (0..progress.done.len())
+                .filter(|&i| !progress.done[i])
+                .map(|i| (i, progress.range_of(i)))
+                .collect()
+        };
+        if pending.len() != progress.lock().await.done.len() {
+            log::info!("resuming {} ({} chunks left)", self.video_details.video_id, pending.len());
+        }
+
+        // Set once a chunk discovers the server is ignoring `Range`, so the
+        // remaining chunks bail out and the whole download retries sequentially.
+        let ignored_ranges = Arc::new(AtomicBool::new(false));
+
+        let downloads = futures::stream::iter(pending)
+            .map(|(index, range)| {
+                let progress = Arc::clone(&progress);
+                let ignored_ranges = Arc::clone(&ignored_ranges);
+                async move {
+                    if ignored_ranges.load(Ordering::Acquire) {
+                        return Ok(());
+                    }
+                    if self.download_range_to(path, range, &opts.retry, on_bytes).await? {
+                        ignored_ranges.store(true, Ordering::Release);
+                        return Ok(());
+                    }
+                    progress.lock().await.mark(index).await?;
+                    Ok::<(), Error>(())
+                }
+            })
+            .buffer_unordered(opts.parallelism);
+
+        downloads
+            .try_for_each(|_| futures::future::ready(Ok(())))
+            .await?;
+
+        if ignored_ranges.load(Ordering::Acquire) {
+            log::warn!("server ignored the Range header, falling back to sequential download");
+            progress.lock().await.remove().await;
+            return self.download_sequential(path, on_bytes).await;
+        }
+
+        progress.lock().await.remove().await;
+        log::info!(
+            "downloaded {} successfully to {:?}",
+            self.video_details.video_id, path
         );
+        Ok(())
+    }
+
+    /// Downloads the bytes in `range` into `file`, writing them at the offset
+    /// `range.start` so the chunk lands in the right place regardless of
+    /// completion order.
+    #[cfg(feature = "download")]
+    pub async fn download_range(&self, range: Range<u64>, file: &mut File) -> Result<()> {
+        if self.download_range_with(range, file, &RetryPolicy::default(), &noop_progress).await? {
+            return Err(Error::UnexpectedResponse(
+                "server ignored the Range header and returned the full body".into()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Downloads `range` into `file`, returning `true` when the server ignored
+    /// the `Range` header (no bytes are written in that case, so the caller can
+    /// fall back to a sequential download).
+    #[cfg(feature = "download")]
+    async fn download_range_with(&self, range: Range<u64>, file: &mut File, retry: &RetryPolicy, on_bytes: &(dyn Fn(i64) + Send + Sync)) -> Result<bool> {
+        log::trace!("download_range: {:?}", range);
+        // The `Range` header is inclusive on both ends, whereas `Range<u64>` is
+        // half-open, so the upper bound is the last byte we want.
+        let last = range.end.saturating_sub(1);
+        let start = range.start;
+        let url = self.signature_cipher.url.as_str().to_owned();
+
+        let status = self.send_and_stream(
+            || self.client
+                .get(&url)
+                .header(reqwest::header::RANGE, format!("bytes={}-{}", start, last)),
+            file,
+            start,
+            retry,
+            true,
+            on_bytes,
+        ).await?;
+
+        Ok(status != reqwest::StatusCode::PARTIAL_CONTENT)
+    }
+
+    /// Opens `path` on its own and writes `range` into it, used by the
+    /// concurrent downloader so that each chunk owns an independent file handle.
+    /// Returns `true` when the server ignored the `Range` header.
+    #[cfg(feature = "download")]
+    async fn download_range_to<P: AsRef<Path>>(&self, path: P, range: Range<u64>, retry: &RetryPolicy, on_bytes: &(dyn Fn(i64) + Send + Sync)) -> Result<bool> {
+        let mut file = tokio::fs::OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .await?;
+        self.download_range_with(range, &mut file, retry, on_bytes).await
+    }
+
+    /// Probes whether the resource can be fetched in ranges by issuing a
+    /// minimal `Range: bytes=0-0` request and checking for a `206` reply.
+    ///
+    /// The `Accept-Ranges` header is advisory and most googlevideo hosts honor
+    /// `Range` without ever sending it, so relying on it would disable
+    /// chunking for almost every real stream. A live ranged request is the
+    /// same signal [`send_and_stream`](Stream::send_and_stream) already uses
+    /// via `require_partial` to detect a range-ignoring server.
+    #[cfg(feature = "download")]
+    async fn accepts_ranges(&self) -> Result<bool> {
+        let status = self.send_with_retry(
+            || self.client
+                .get(self.signature_cipher.url.as_str())
+                .header(reqwest::header::RANGE, "bytes=0-0"),
+            &RetryPolicy::default(),
+        ).await?.status();
+
+        Ok(status == reqwest::StatusCode::PARTIAL_CONTENT)
+    }
+
+    /// Downloads this (video) `Stream` together with an `audio` track and muxes
+    /// them into a single file at `path` using ffmpeg.
+    ///
+    /// `self` must carry a video track and `audio` an audio track — typically
+    /// two adaptive DASH formats — so that the highest-quality video can be
+    /// paired with a separate audio track instead of settling for a lower
+    /// bitrate progressive format. Both tracks are downloaded to temporary
+    /// files, combined with `ffmpeg -i video -i audio -c copy`, and the
+    /// intermediate files are removed afterwards. When `-c copy` fails because
+    /// the containers are incompatible, ffmpeg is retried with a re-encode.
+    ///
+    /// Returns [`Error::MissingFFmpeg`] when no `ffmpeg` binary is found on
+    /// `PATH` — checked up front, before either track is downloaded, so a
+    /// missing `ffmpeg` is reported immediately instead of after fetching
+    /// potentially hundreds of megabytes.
+    #[cfg(feature = "mux")]
+    pub async fn download_muxed_with<P: AsRef<Path>>(&self, audio: &Stream, path: P) -> Result<()> {
+        mux::ensure_ffmpeg().await?;
+
+        let video_tmp = mux::temp_path(self);
+        let audio_tmp = mux::temp_path(audio);
 
-        let mut url = self.signature_cipher.url.clone();
+        // Run both downloads and the mux inside one fallible block so cleanup
+        // always runs — otherwise a failed audio download would leak the
+        // (potentially multi-hundred-MB) video temp file via the `?`.
+        let result = async {
+            self.download_to(&video_tmp).await?;
+            audio.download_to(&audio_tmp).await?;
+            mux::mux_files(&video_tmp, &audio_tmp, path.as_ref()).await
+        }.await;
+
+        // Best-effort cleanup of the intermediate tracks regardless of outcome.
+        let _ = tokio::fs::remove_file(&video_tmp).await;
+        let _ = tokio::fs::remove_file(&audio_tmp).await;
+
+        result
+    }
+
+    /// Attempts to download the `Stream`s resource to `path`, reporting
+    /// progress and lifecycle events to `callback`.
+    ///
+    /// [`on_start`](DownloadCallback::on_start) is fired with the total size
+    /// (when known), [`on_progress`](DownloadCallback::on_progress) after each
+    /// written block with the running byte count, and
+    /// [`on_complete`](DownloadCallback::on_complete) with the resolved output
+    /// path once the file is finalized.
+    #[cfg(feature = "callback")]
+    pub async fn download_to_with_callback<P, C>(&self, path: P, callback: C) -> Result<()>
+        where
+            P: AsRef<Path>,
+            C: DownloadCallback {
+        let path = path.as_ref();
+        log::trace!("download_to_with_callback: {:?}", path);
+
+        let content_length = self.content_length().await.ok();
+        let callback = std::sync::Mutex::new(callback);
+        callback.lock().unwrap().on_start(content_length);
+
+        let counter = std::sync::atomic::AtomicU64::new(0);
+        let on_bytes = |n: i64| {
+            // `n` may be negative: a body retried after a mid-stream failure
+            // gives back whatever it already reported, so the aggregate never
+            // overshoots `content_length`.
+            let total = if n >= 0 {
+                counter.fetch_add(n as u64, std::sync::atomic::Ordering::Relaxed) + n as u64
+            } else {
+                let delta = (-n) as u64;
+                counter.fetch_sub(delta, std::sync::atomic::Ordering::Relaxed) - delta
+            };
+            callback.lock().unwrap().on_progress(total, content_length);
+        };
+
+        // OTF streams are segmented, so they're assembled via `download_otf_with`
+        // up front. A plain (non-OTF) stream still gets the same 404 fallback
+        // as the plain `download_to` path, since a `GET` 404ing on a segmented
+        // resource is only discovered once it's actually requested.
+        let result = async {
+            let mut file = File::create(path).await?;
+            if self.is_otf {
+                self.download_otf_with(&mut file, &on_bytes).await
+            } else {
+                match self.download_full(&self.signature_cipher.url, &mut file, &on_bytes).await {
+                    Err(Error::Request(e)) if e.status().contains(&reqwest::StatusCode::NOT_FOUND) => {
+                        self.download_otf_with(&mut file, &on_bytes).await
+                    }
+                    result => result,
+                }
+            }
+        }.await;
+
+        match result {
+            Ok(()) => {
+                callback.lock().unwrap().on_complete(path);
+                log::info!(
+                    "downloaded {} successfully to {:?}",
+                    self.video_details.video_id, path
+                );
+                Ok(())
+            }
+            Err(e) => {
+                log::error!("failed to download {}: {:?}", self.video_details.video_id, e);
+                let _ = tokio::fs::remove_file(path).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Attempts the concurrent, ranged download of
+    /// [`download_to_with_opts`](Stream::download_to_with_opts) while reporting
+    /// progress and lifecycle events to `callback`.
+    ///
+    /// A single byte counter aggregates [`on_progress`](DownloadCallback::on_progress)
+    /// across all chunks, so throughput/ETA can be shown even though chunks
+    /// complete out of order.
+    #[cfg(all(feature = "download", feature = "callback"))]
+    pub async fn download_to_with_opts_and_callback<P, C>(&self, path: P, opts: &DownloadOpts, callback: C) -> Result<()>
+        where
+            P: AsRef<Path>,
+            C: DownloadCallback {
+        let path = path.as_ref();
+        log::trace!("download_to_with_opts_and_callback: {:?}", path);
+
+        let content_length = self.content_length().await.ok();
+        let callback = std::sync::Mutex::new(callback);
+        callback.lock().unwrap().on_start(content_length);
+
+        let counter = std::sync::atomic::AtomicU64::new(0);
+        let on_bytes = |n: i64| {
+            // `n` may be negative: a body retried after a mid-stream failure
+            // gives back whatever it already reported, so the aggregate never
+            // overshoots `content_length`.
+            let total = if n >= 0 {
+                counter.fetch_add(n as u64, std::sync::atomic::Ordering::Relaxed) + n as u64
+            } else {
+                let delta = (-n) as u64;
+                counter.fetch_sub(delta, std::sync::atomic::Ordering::Relaxed) - delta
+            };
+            callback.lock().unwrap().on_progress(total, content_length);
+        };
+
+        self.download_chunked(path, opts, &on_bytes).await?;
+
+        callback.lock().unwrap().on_complete(path);
+        Ok(())
+    }
+
+    /// Downloads an OTF/DASH segmented stream into `file`.
+    ///
+    /// The initialization and index segments are requested first via the
+    /// known [`init_range`](Stream::init_range) and
+    /// [`index_range`](Stream::index_range) byte ranges, so the output starts
+    /// with a valid header. The media segments are then walked by sequence
+    /// number (`&sq=N`) — concatenating each segment's bytes in order — until a
+    /// segment returns `204`/`404`, which marks the end of the manifest.
+    #[cfg(feature = "download")]
+    async fn download_otf_with(&self, file: &mut File, on_bytes: &(dyn Fn(i64) + Send + Sync)) -> Result<()> {
+        log::trace!("download_otf: {}", self.signature_cipher.url.as_str());
+
+        // Tracks the running total so it can be checked against
+        // `content_length` once the manifest ends, in addition to forwarding
+        // every write on to the caller's `on_bytes`. An atomic (rather than a
+        // `Cell`) is used purely to satisfy the `Send + Sync` bound the
+        // `on_bytes` callbacks share across this codebase.
+        let written = std::sync::atomic::AtomicI64::new(0);
+        let on_bytes = |n: i64| {
+            written.fetch_add(n, std::sync::atomic::Ordering::Relaxed);
+            on_bytes(n);
+        };
+
+        // Addressing model: `init_range`/`index_range` describe the leading
+        // header bytes of the resource (the initialization segment followed by
+        // the contiguous sidx index, at offsets `0..init.end` and
+        // `init.end..index.end`), while the media payload that follows is
+        // addressed by sequence number (`&sq=N`). Both live in the *same*
+        // resource, laid out header-then-segments, so fetching the byte ranges
+        // first and then appending the sequenced segments reconstructs the file
+        // in order. Because `sq=0` re-serves that same header, we skip it once
+        // either header range has already been fetched to avoid duplicating it.
+        let mut fetched_header = false;
+        if let Some(init) = self.init_range.clone() {
+            self.fetch_otf_range(init, file, &on_bytes).await?;
+            fetched_header = true;
+        }
+        if let Some(index) = self.index_range.clone() {
+            self.fetch_otf_range(index, file, &on_bytes).await?;
+            fetched_header = true;
+        }
+        let mut seq = if fetched_header { 1 } else { 0 };
+
+        let url = self.signature_cipher.url.clone();
         let base_query = url
             .query()
             .map(str::to_owned)
-            .unwrap_or_else(|| String::new());
-
-        // The 0th sequential request provides the file headers, which tell us
-        // information about how the file is segmented.
-        Self::set_url_seq_query(&mut url, &base_query, 0);
-        let res = self.get(&url).await?;
-        let segment_count = Stream::extract_segment_count(&res)?;
-        Self::write_stream_to_file(res.bytes_stream(), file).await?;
-
-        for i in 1..segment_count {
-            Self::set_url_seq_query(&mut url, &base_query, i);
-            self.download_full(&url, file).await?;
+            .unwrap_or_else(String::new);
+
+        loop {
+            let mut seg_url = url.clone();
+            Self::set_url_seq_query(&mut seg_url, &base_query, seq);
+
+            match self.get(&seg_url).await {
+                Ok(res) if res.status() == reqwest::StatusCode::NO_CONTENT => break,
+                Ok(res) => {
+                    let write_on_bytes = |n: usize| on_bytes(n as i64);
+                    Self::write_stream_to_file_with(res.bytes_stream(), file, &write_on_bytes).await?;
+                    seq += 1;
+                }
+                Err(Error::Request(e)) if e.status().contains(&reqwest::StatusCode::NOT_FOUND) => break,
+                Err(e) => return Err(e),
+            }
+        }
+
+        // `content_length`, when present, describes the whole OTF resource
+        // (header plus every media segment), so a manifest that ended early —
+        // e.g. a premature 404 partway through — can be caught by comparing
+        // against it. Only the lower bound is checked, since a retried
+        // segment can be requested more than once without the extra bytes
+        // affecting correctness.
+        if let Some(expected) = self.content_length {
+            let written = written.load(std::sync::atomic::Ordering::Relaxed).max(0) as u64;
+            if written < expected {
+                return Err(Error::UnexpectedResponse(format!(
+                    "OTF download ended early: wrote {} of {} expected bytes",
+                    written, expected
+                )));
+            }
         }
 
         Ok(())
     }
 
+    /// Fetches a known byte range of an OTF resource (the init or index
+    /// segment), reporting progress and treating a server that ignores the
+    /// `Range` header as an error since the header bytes are mandatory.
+    ///
+    /// The underlying detection is offset-independent, so a server that ignores
+    /// `Range` for the init segment (which starts at offset `0`) is caught here
+    /// rather than silently writing its entire body in place of the init bytes.
+    #[cfg(feature = "download")]
+    async fn fetch_otf_range(&self, range: Range<u64>, file: &mut File, on_bytes: &(dyn Fn(i64) + Send + Sync)) -> Result<()> {
+        if self.download_range_with(range, file, &RetryPolicy::default(), on_bytes).await? {
+            return Err(Error::UnexpectedResponse(
+                "server ignored the Range header while fetching the OTF header".into()
+            ));
+        }
+        Ok(())
+    }
+
     #[inline]
     #[cfg(feature = "download")]
-    async fn download_full(&self, url: &url::Url, file: &mut File) -> Result<()> {
-        let res = self.get(url).await?;
-        Self::write_stream_to_file(res.bytes_stream(), file).await
+    async fn download_full(&self, url: &url::Url, file: &mut File, on_bytes: &(dyn Fn(i64) + Send + Sync)) -> Result<()> {
+        let url = url.as_str().to_owned();
+        self.send_and_stream(
+            || self.client.get(&url),
+            file,
+            0,
+            &RetryPolicy::default(),
+            false,
+            on_bytes,
+        ).await?;
+        Ok(())
     }
 
     #[inline]
     #[cfg(feature = "download")]
     async fn get(&self, url: &url::Url) -> Result<reqwest::Response> {
         log::trace!("get: {}", url.as_str());
-        Ok(
-            self.client
-                .get(url.as_str())
-                .send()
-                .await?
-                .error_for_status()?
-        )
+        self.send_with_retry(
+            || self.client.get(url.as_str()),
+            &RetryPolicy::default(),
+        ).await
+    }
+
+    /// Sends a freshly-built request, retrying transient failures with
+    /// exponential backoff as described by `retry`. The body is not consumed,
+    /// so this only covers header-level failures — use
+    /// [`send_and_stream`](Stream::send_and_stream) when the body must also be
+    /// retried.
+    #[cfg(feature = "download")]
+    async fn send_with_retry(
+        &self,
+        build: impl Fn() -> reqwest::RequestBuilder,
+        retry: &RetryPolicy,
+    ) -> Result<reqwest::Response> {
+        let mut backoff = Backoff::new(retry);
+        loop {
+            let result = async {
+                Ok::<_, Error>(
+                    build()
+                        .send()
+                        .await?
+                        .error_for_status()?
+                )
+            }.await;
+
+            match result {
+                Ok(res) => return Ok(res),
+                Err(e) => backoff.retry_or(e).await?,
+            }
+        }
+    }
+
+    /// Sends a freshly-built request and streams its body into `file` at
+    /// `offset`, retrying the whole operation — send, status check, and body
+    /// streaming — on transient failures.
+    ///
+    /// This is what makes a mid-stream connection drop or truncated body on a
+    /// multi-gigabyte download recoverable rather than fatal: each attempt
+    /// re-seeks to `offset` and re-streams from scratch. When `require_partial`
+    /// is set the request carried a `Range` header, so any non-`206` reply is
+    /// treated as the server having ignored the range — the body is left
+    /// unwritten and the status is returned so the caller can fall back. This
+    /// check is independent of `offset`, so it also catches a range-ignoring
+    /// server on the very first (offset `0`) chunk.
+    ///
+    /// `on_bytes` is given a signed delta rather than a plain count: a failed
+    /// attempt re-streams the body from `offset` on retry, so before retrying
+    /// this gives back whatever the failed attempt already reported, keeping a
+    /// caller's running total from overshooting past what was actually
+    /// written.
+    #[cfg(feature = "download")]
+    async fn send_and_stream(
+        &self,
+        build: impl Fn() -> reqwest::RequestBuilder,
+        file: &mut File,
+        offset: u64,
+        retry: &RetryPolicy,
+        require_partial: bool,
+        on_bytes: &(dyn Fn(i64) + Send + Sync),
+    ) -> Result<reqwest::StatusCode> {
+        let mut backoff = Backoff::new(retry);
+        loop {
+            let written_this_attempt = std::sync::atomic::AtomicU64::new(0);
+            let attempt_on_bytes = |n: usize| {
+                written_this_attempt.fetch_add(n as u64, std::sync::atomic::Ordering::Relaxed);
+                on_bytes(n as i64);
+            };
+
+            let result = async {
+                let res = build()
+                    .send()
+                    .await?
+                    .error_for_status()?;
+                let status = res.status();
+
+                // A server that ignores the `Range` header answers `200` with
+                // the whole body instead of `206`; streaming that in would
+                // replace a single chunk with the entire file, so report the
+                // status without writing and let the caller fall back.
+                if require_partial && status != reqwest::StatusCode::PARTIAL_CONTENT {
+                    return Ok::<_, Error>(status);
+                }
+
+                file.seek(std::io::SeekFrom::Start(offset)).await?;
+                Self::write_stream_to_file_with(res.bytes_stream(), file, &attempt_on_bytes).await?;
+                Ok(status)
+            }.await;
+
+            match result {
+                Ok(status) => return Ok(status),
+                Err(e) => {
+                    let written = written_this_attempt.load(std::sync::atomic::Ordering::Relaxed);
+                    if written > 0 {
+                        on_bytes(-(written as i64));
+                    }
+                    backoff.retry_or(e).await?
+                }
+            }
+        }
+    }
+
+    /// Whether a failed request should be retried: timeouts, connection errors,
+    /// truncated bodies (no status), and `5xx`/`429` responses are transient;
+    /// `404` and every other `4xx` are not.
+    #[inline]
+    #[cfg(feature = "download")]
+    fn is_transient(e: &reqwest::Error) -> bool {
+        if e.is_timeout() || e.is_connect() {
+            return true;
+        }
+        match e.status() {
+            Some(status) => {
+                status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            }
+            None => true,
+        }
+    }
+
+    /// Whether a crate-level error wraps a transient request failure. Errors
+    /// raised while streaming the body surface here as [`Error::Request`].
+    #[inline]
+    #[cfg(feature = "download")]
+    fn is_transient_err(e: &Error) -> bool {
+        match e {
+            Error::Request(req) => Self::is_transient(req),
+            _ => false,
+        }
+    }
+
+    #[inline]
+    #[cfg(feature = "download")]
+    fn jitter() -> f64 {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        // A per-process counter mixed with the wall clock seeds an xorshift*
+        // step, so concurrent chunk/segment retries diverge instead of backing
+        // off in lock-step — without pulling in an rng dependency. The
+        // `fetch_add` guarantees distinct seeds even within the same nanosecond.
+        static STATE: AtomicU64 = AtomicU64::new(0x2545_F491_4F6C_DD1D);
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+
+        let mut x = STATE.fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed) ^ nanos;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        let r = x.wrapping_mul(0x2545_F491_4F6C_DD1D);
+        // Keep the top 53 bits for a uniform value in [0, 1).
+        (r >> 11) as f64 / ((1u64 << 53) as f64)
     }
 
     #[inline]
     #[cfg(feature = "download")]
-    async fn write_stream_to_file(mut stream: impl FuturesStream<Item=reqwest::Result<bytes::Bytes>> + Unpin, file: &mut File) -> Result<()> {
+    async fn write_stream_to_file_with(
+        mut stream: impl FuturesStream<Item=reqwest::Result<bytes::Bytes>> + Unpin,
+        file: &mut File,
+        on_bytes: &(dyn Fn(usize) + Send + Sync),
+    ) -> Result<()> {
         while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
             file
-                .write_all(&chunk?)
+                .write_all(&chunk)
                 .await?;
+            on_bytes(chunk.len());
         }
+        // `tokio::fs::File` finishes a write in the background; wait for it so
+        // the bytes are in the file before the caller records them as done.
+        file.flush().await?;
         Ok(())
     }
 
@@ -245,27 +981,6 @@ impl Stream {
             .append_pair("sq", &sq.to_string());
     }
 
-    #[inline]
-    #[cfg(feature = "download")]
-    fn extract_segment_count(res: &reqwest::Response) -> Result<u64> {
-        Ok(
-            res
-                .headers()
-                .get("Segment-Count")
-                .ok_or_else(|| Error::UnexpectedResponse(
-                    "sequence download request did not contain a Segment-Count".into()
-                ))?
-                .to_str()
-                .map_err(|_| Error::UnexpectedResponse(
-                    "Segment-Count is not valid utf-8".into()
-                ))?
-                .parse::<u64>()
-                .map_err(|_| Error::UnexpectedResponse(
-                    "Segment-Count could not be parsed into an integer".into()
-                ))?
-        )
-    }
-
     #[inline]
     #[cfg(feature = "download")]
     pub async fn content_length(&self) -> Result<u64> {
@@ -305,6 +1020,168 @@ impl Stream {
     }
 }
 
+/// Tracks which fixed-size chunks of a ranged download have completed, backed
+/// by a sidecar file so an interrupted download can resume correctly.
+///
+/// The output file's length is *not* a valid completeness marker here: chunks
+/// are written out of order, so a later chunk finishing first leaves a sparse
+/// hole that still counts towards the length. Instead each chunk is recorded
+/// only once its bytes are on disk, and resume re-fetches every chunk not
+/// explicitly marked done. The sidecar embeds the chunk size and total length
+/// so a mismatched sidecar (different options, or a different video) is
+/// discarded rather than trusted.
+#[cfg(feature = "download")]
+struct ChunkProgress {
+    path: PathBuf,
+    chunk_size: u64,
+    content_length: u64,
+    done: Vec<bool>,
+}
+
+#[cfg(feature = "download")]
+impl ChunkProgress {
+    const MAGIC: &'static [u8; 8] = b"RTPART01";
+    const HEADER_LEN: usize = 24;
+
+    fn sidecar_path(data_path: &Path) -> PathBuf {
+        let mut name = data_path.as_os_str().to_owned();
+        name.push(".rtpart");
+        PathBuf::from(name)
+    }
+
+    async fn load(data_path: &Path, chunk_size: u64, content_length: u64) -> Result<Self> {
+        let total = ((content_length + chunk_size - 1) / chunk_size) as usize;
+        let path = Self::sidecar_path(data_path);
+
+        let done = match tokio::fs::read(&path).await {
+            Ok(bytes) if Self::header_matches(&bytes, chunk_size, content_length)
+                && bytes.len() == Self::HEADER_LEN + total => {
+                bytes[Self::HEADER_LEN..].iter().map(|&b| b != 0).collect()
+            }
+            _ => vec![false; total],
+        };
+
+        Ok(Self { path, chunk_size, content_length, done })
+    }
+
+    #[inline]
+    fn header_matches(bytes: &[u8], chunk_size: u64, content_length: u64) -> bool {
+        bytes.len() >= Self::HEADER_LEN
+            && &bytes[0..8] == Self::MAGIC
+            && bytes[8..16] == chunk_size.to_le_bytes()
+            && bytes[16..24] == content_length.to_le_bytes()
+    }
+
+    #[inline]
+    fn is_complete(&self) -> bool {
+        self.done.iter().all(|&d| d)
+    }
+
+    #[inline]
+    fn range_of(&self, index: usize) -> Range<u64> {
+        let start = index as u64 * self.chunk_size;
+        let end = (start + self.chunk_size).min(self.content_length);
+        start..end
+    }
+
+    fn serialize(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(Self::HEADER_LEN + self.done.len());
+        buf.extend_from_slice(Self::MAGIC);
+        buf.extend_from_slice(&self.chunk_size.to_le_bytes());
+        buf.extend_from_slice(&self.content_length.to_le_bytes());
+        buf.extend(self.done.iter().map(|&d| d as u8));
+        buf
+    }
+
+    async fn mark(&mut self, index: usize) -> Result<()> {
+        self.done[index] = true;
+        tokio::fs::write(&self.path, self.serialize()).await?;
+        Ok(())
+    }
+
+    async fn remove(&self) {
+        let _ = tokio::fs::remove_file(&self.path).await;
+    }
+}
+
+/// A lazily-constructed runtime used to drive the async download path from the
+/// blocking API, mirroring the `reqwest::blocking` pattern.
+#[cfg(feature = "blocking")]
+static BLOCKING_RUNTIME: once_cell::sync::Lazy<tokio::runtime::Runtime> =
+    once_cell::sync::Lazy::new(|| {
+        tokio::runtime::Runtime::new().expect("failed to construct the blocking runtime")
+    });
+
+#[cfg(feature = "blocking")]
+impl Stream {
+    /// The blocking equivalent of [`download`](Stream::download).
+    #[inline]
+    pub fn blocking_download(&self) -> Result<PathBuf> {
+        BLOCKING_RUNTIME.block_on(self.download())
+    }
+
+    /// The blocking equivalent of [`download_to`](Stream::download_to).
+    #[inline]
+    pub fn blocking_download_to<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        BLOCKING_RUNTIME.block_on(self.download_to(path))
+    }
+
+    /// The blocking equivalent of
+    /// [`download_to_with_opts`](Stream::download_to_with_opts).
+    #[inline]
+    pub fn blocking_download_to_with_opts<P: AsRef<Path>>(&self, path: P, opts: &DownloadOpts) -> Result<()> {
+        BLOCKING_RUNTIME.block_on(self.download_to_with_opts(path, opts))
+    }
+
+    /// The blocking equivalent of [`content_length`](Stream::content_length).
+    #[inline]
+    pub fn blocking_content_length(&self) -> Result<u64> {
+        BLOCKING_RUNTIME.block_on(self.content_length())
+    }
+}
+
+#[cfg(all(feature = "blocking", feature = "callback"))]
+impl Stream {
+    /// The blocking equivalent of
+    /// [`download_to_with_callback`](Stream::download_to_with_callback).
+    ///
+    /// The callback is invoked on the calling thread while it is inside the
+    /// internal runtime, so it must not itself block on that runtime.
+    #[inline]
+    pub fn blocking_download_to_with_callback<P, C>(&self, path: P, callback: C) -> Result<()>
+        where
+            P: AsRef<Path>,
+            C: DownloadCallback {
+        BLOCKING_RUNTIME.block_on(self.download_to_with_callback(path, callback))
+    }
+
+    /// The blocking equivalent of
+    /// [`download_to_with_opts_and_callback`](Stream::download_to_with_opts_and_callback).
+    #[inline]
+    pub fn blocking_download_to_with_opts_and_callback<P, C>(&self, path: P, opts: &DownloadOpts, callback: C) -> Result<()>
+        where
+            P: AsRef<Path>,
+            C: DownloadCallback {
+        BLOCKING_RUNTIME.block_on(self.download_to_with_opts_and_callback(path, opts, callback))
+    }
+}
+
+#[cfg(all(feature = "blocking", feature = "mux"))]
+impl Stream {
+    /// The blocking equivalent of
+    /// [`download_muxed_with`](Stream::download_muxed_with).
+    #[inline]
+    pub fn blocking_download_muxed_with<P: AsRef<Path>>(&self, audio: &Stream, path: P) -> Result<()> {
+        BLOCKING_RUNTIME.block_on(self.download_muxed_with(audio, path))
+    }
+}
+
+/// A progress sink that discards its byte counts, used wherever no callback is
+/// installed.
+#[inline]
+#[cfg(feature = "download")]
+fn noop_progress(_: i64) {}
+
 #[inline]
 fn is_adaptive(codecs: &Vec<String>) -> bool {
     codecs.len() % 2 != 0
@@ -323,4 +1200,55 @@ fn includes_audio_track(codecs: &Vec<String>, mime: &Mime) -> bool {
 #[inline]
 fn is_progressive(codecs: &Vec<String>) -> bool {
     !is_adaptive(codecs)
-}
\ No newline at end of file
+}
+
+#[cfg(all(test, feature = "download"))]
+mod tests {
+    use super::ChunkProgress;
+
+    #[tokio::test]
+    async fn chunk_progress_round_trips_through_its_sidecar() {
+        let dir = std::env::temp_dir().join(format!("rustube-test-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let data_path = dir.join("data.bin");
+
+        let mut progress = ChunkProgress::load(&data_path, 10, 25).await.unwrap();
+        assert_eq!(progress.done.len(), 3);
+        assert!(!progress.is_complete());
+
+        progress.mark(1).await.unwrap();
+        assert_eq!(progress.done, vec![false, true, false]);
+
+        let reloaded = ChunkProgress::load(&data_path, 10, 25).await.unwrap();
+        assert_eq!(reloaded.done, vec![false, true, false]);
+
+        progress.remove().await;
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[tokio::test]
+    async fn chunk_progress_discards_a_sidecar_from_different_options() {
+        let dir = std::env::temp_dir().join(format!("rustube-test-mismatch-{}", std::process::id()));
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let data_path = dir.join("data.bin");
+
+        let mut progress = ChunkProgress::load(&data_path, 10, 25).await.unwrap();
+        progress.mark(0).await.unwrap();
+
+        // Same data path, different chunk size: the sidecar no longer matches
+        // and must be treated as absent rather than misread.
+        let reloaded = ChunkProgress::load(&data_path, 5, 25).await.unwrap();
+        assert_eq!(reloaded.done, vec![false; 5]);
+
+        progress.remove().await;
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+    }
+
+    #[test]
+    fn jitter_is_within_the_unit_interval() {
+        for _ in 0..1000 {
+            let value = super::Stream::jitter();
+            assert!(value >= 0.0 && value < 1.0, "jitter() returned {}", value);
+        }
+    }
+}
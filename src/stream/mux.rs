@@ -0,0 +1,111 @@
+use std::path::{Path, PathBuf};
+
+use tokio::process::Command;
+
+use crate::Result;
+use crate::error::Error;
+use crate::stream::Stream;
+
+/// Selects the best video-only and audio-only `Stream`s out of a slice and
+/// muxes them into a single file.
+///
+/// Adaptive (DASH) formats carry video and audio in separate tracks, so the
+/// highest-quality video is only available muxed with a matching audio track.
+/// The `Muxer` picks the highest-resolution video-only `Stream` and the
+/// highest-bitrate audio-only `Stream`, then delegates to
+/// [`Stream::download_muxed_with`].
+#[derive(Clone, Debug)]
+pub struct Muxer<'a> {
+    video: &'a Stream,
+    audio: &'a Stream,
+}
+
+impl<'a> Muxer<'a> {
+    /// Picks the best video-only and audio-only pair from `streams`.
+    ///
+    /// Returns [`Error::UnexpectedResponse`] when either a video-only or an
+    /// audio-only `Stream` is missing from the slice.
+    pub fn best(streams: &'a [Stream]) -> Result<Self> {
+        let video = streams
+            .iter()
+            .filter(|s| s.includes_video_track && !s.includes_audio_track)
+            .max_by_key(|s| s.resolution.unwrap_or(0))
+            .ok_or_else(|| Error::UnexpectedResponse(
+                "no video-only stream available to mux".into()
+            ))?;
+        let audio = streams
+            .iter()
+            .filter(|s| s.includes_audio_track && !s.includes_video_track)
+            .max_by_key(|s| s.abr.unwrap_or(0))
+            .ok_or_else(|| Error::UnexpectedResponse(
+                "no audio-only stream available to mux".into()
+            ))?;
+
+        Ok(Self { video, audio })
+    }
+
+    /// The selected video-only `Stream`.
+    #[inline]
+    pub fn video(&self) -> &Stream {
+        self.video
+    }
+
+    /// The selected audio-only `Stream`.
+    #[inline]
+    pub fn audio(&self) -> &Stream {
+        self.audio
+    }
+
+    /// Downloads the selected pair and muxes them into `path`.
+    #[inline]
+    pub async fn download_to<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.video.download_muxed_with(self.audio, path).await
+    }
+}
+
+/// Invokes ffmpeg to combine an already-downloaded video and audio file into
+/// `out`, copying the streams when possible and re-encoding as a fallback.
+pub(crate) async fn mux_files(video: &Path, audio: &Path, out: &Path) -> Result<()> {
+    // `-c copy` only works when the codecs already fit the output container;
+    // when it does not, ffmpeg exits non-zero and we retry with a re-encode.
+    if run_ffmpeg(video, audio, out, &["-c", "copy"]).await.is_ok() {
+        return Ok(());
+    }
+
+    log::warn!("`-c copy` muxing failed, retrying with a re-encode");
+    run_ffmpeg(video, audio, out, &[]).await
+}
+
+async fn run_ffmpeg(video: &Path, audio: &Path, out: &Path, codec_args: &[&str]) -> Result<()> {
+    let status = Command::new("ffmpeg")
+        .arg("-y")
+        .arg("-i").arg(video)
+        .arg("-i").arg(audio)
+        .args(codec_args)
+        .arg(out)
+        .status()
+        .await?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::Muxing(format!("ffmpeg exited with {}", status)))
+    }
+}
+
+/// Confirms that an `ffmpeg` binary is reachable on `PATH`.
+pub(crate) async fn ensure_ffmpeg() -> Result<()> {
+    Command::new("ffmpeg")
+        .arg("-version")
+        .output()
+        .await
+        .map(|_| ())
+        .map_err(|_| Error::MissingFFmpeg)
+}
+
+/// Builds a temporary file path for an intermediate track download.
+pub(crate) fn temp_path(stream: &Stream) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "rustube-{}-{}.tmp", stream.video_details.video_id, stream.itag
+    ))
+}